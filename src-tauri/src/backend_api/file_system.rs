@@ -1,6 +1,7 @@
 use crate::{
     StateManager,
     error::{MutexLockError, PathError, Result},
+    file_index::{QUICK_OPEN_LIMIT, QuickOpenResults},
     files_api::get_directory_items,
 };
 use home::home_dir;
@@ -9,14 +10,27 @@ use std::path::PathBuf;
 
 use super::responses::FileSystemInfo;
 use super::responses::OpenFile;
+use super::walking::queue_stale_walks;
 
-pub fn _get_file_system_info(dir: Option<String>) -> Result<FileSystemInfo> {
+pub fn _get_file_system_info(
+    state: tauri::State<StateManager>,
+    dir: Option<String>,
+) -> Result<FileSystemInfo> {
     if let Some(dir) = dir {
         let mut path_buf = PathBuf::from(dir);
         if !path_buf.is_absolute() {
             path_buf = path_buf.absolutize().unwrap().to_path_buf();
         }
         let directory_items = get_directory_items(&path_buf, 4)?;
+
+        // The explorer directory is the project root that quick open searches
+        state
+            .file_index
+            .lock()
+            .map_err(|err| MutexLockError(err.to_string()))?
+            .set_roots(vec![path_buf.clone()]);
+        queue_stale_walks(&state)?;
+
         return Ok(FileSystemInfo::create(
             path_buf
                 .into_os_string()
@@ -62,3 +76,13 @@ pub fn _close_file(state: tauri::State<StateManager>, id: u32) -> Result<()> {
     file_manager.close_file(&id);
     Ok(())
 }
+
+pub fn _quick_open(state: tauri::State<StateManager>, query: String) -> Result<QuickOpenResults> {
+    // Roots not walked yet answer from the files of their last walk, if any
+    queue_stale_walks(&state)?;
+    let mut file_index = state
+        .file_index
+        .lock()
+        .map_err(|err| MutexLockError(err.to_string()))?;
+    Ok(file_index.quick_open(&query, QUICK_OPEN_LIMIT))
+}
@@ -1,12 +1,18 @@
 use crate::StateManager;
-use crate::backend_api::file_system::{_close_file, _get_file_system_info, _open_file};
+use crate::backend_api::file_system::{
+    _close_file, _get_file_system_info, _open_file, _quick_open,
+};
 use crate::backend_api::responses::{FileSystemInfo, OpenFile};
 use crate::error::Response;
+use crate::file_index::QuickOpenResults;
 
 #[tauri::command]
 #[specta::specta]
-pub fn get_file_system_info(dir: Option<String>) -> Response<FileSystemInfo> {
-    _get_file_system_info(dir).into()
+pub fn get_file_system_info(
+    state: tauri::State<StateManager>,
+    dir: Option<String>,
+) -> Response<FileSystemInfo> {
+    _get_file_system_info(state, dir).into()
 }
 
 #[tauri::command]
@@ -20,3 +26,9 @@ pub fn open_file(state: tauri::State<StateManager>, path: String) -> Response<Op
 pub fn close_file(state: tauri::State<StateManager>, id: u32) -> Response<()> {
     _close_file(state, id).into()
 }
+
+#[tauri::command]
+#[specta::specta]
+pub fn quick_open(state: tauri::State<StateManager>, query: String) -> Response<QuickOpenResults> {
+    _quick_open(state, query).into()
+}
@@ -2,5 +2,6 @@ mod editor_info;
 mod file_data;
 mod file_system;
 pub mod responses;
+pub mod walking;
 
 pub mod middleware;
@@ -0,0 +1,34 @@
+use tauri::Manager;
+
+use crate::{
+    StateManager,
+    error::{MutexLockError, Result},
+    file_index::walk_files,
+};
+
+/// Queues a walk of the roots of the file index that are due for one.
+pub fn queue_stale_walks(state: &StateManager) -> Result<()> {
+    let stale = state
+        .file_index
+        .lock()
+        .map_err(|err| MutexLockError(err.to_string()))?
+        .stale_roots();
+    state.walk_queue.push(stale)?;
+    Ok(())
+}
+
+/// Walks the roots of the file index on a thread of its own, the index is locked only
+/// to swap in the files of a walked root.
+pub fn spawn_file_walker(app: tauri::AppHandle) {
+    std::thread::spawn(move || {
+        let state = app.state::<StateManager>();
+        while let Some(roots) = state.walk_queue.next() {
+            for root in roots {
+                let files = walk_files(&root);
+                if let Ok(mut file_index) = state.file_index.lock() {
+                    file_index.install(&root, files);
+                }
+            }
+        }
+    });
+}
@@ -0,0 +1,420 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+    sync::{Condvar, Mutex},
+    time::{Duration, Instant},
+};
+
+use ignore::WalkBuilder;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::{
+    error::MutexLockError,
+    fuzzy::{FuzzyQuery, char_mask, fuzzy_match, fuzzy_score},
+};
+
+pub const IGNORED_DIRECTORIES: [&str; 5] = [".git", ".hg", ".svn", "node_modules", "target"];
+pub const QUICK_OPEN_LIMIT: usize = 50;
+// Roots are only walked again after this interval so that typing a query does not
+// walk the whole tree on every keystroke
+const REFRESH_INTERVAL: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Serialize, Deserialize, Type, Clone)]
+pub struct QuickOpenItem {
+    path: PathBuf,
+    relative_path: String,
+    score: i32,
+    // Char indices into relative_path
+    positions: Vec<u32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Type, Clone, Default)]
+pub struct QuickOpenResults {
+    items: Vec<QuickOpenItem>,
+    // Set while a root has not been walked yet, more items are on the way
+    indexing: bool,
+}
+
+#[derive(Debug)]
+struct Candidate {
+    path: PathBuf,
+    relative_path: String,
+    chars: Box<[char]>,
+    mask: u64,
+    basename_start: usize,
+}
+
+impl Candidate {
+    fn new(root: &Path, path: PathBuf) -> Self {
+        let relative_path = path
+            .strip_prefix(root)
+            .unwrap_or(&path)
+            .to_string_lossy()
+            .replace('\\', "/");
+        let chars: Box<[char]> = relative_path.chars().collect();
+        let basename_start = chars
+            .iter()
+            .rposition(|c| *c == '/')
+            .map(|index| index + 1)
+            .unwrap_or(0);
+        Self {
+            mask: char_mask(chars.iter().copied()),
+            path,
+            relative_path,
+            chars,
+            basename_start,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct RootIndex {
+    root: PathBuf,
+    candidates: Vec<Candidate>,
+    // When the last walk of the root was queued, none before the first one
+    walked: Option<Instant>,
+    // Whether the files of a walk were installed, the candidates are empty until then
+    indexed: bool,
+    // Candidates matched by the previous query, reused while the user keeps typing
+    last_query: Option<(FuzzyQuery, Vec<u32>)>,
+}
+
+impl RootIndex {
+    fn new(root: PathBuf) -> Self {
+        Self {
+            root,
+            candidates: vec![],
+            walked: None,
+            indexed: false,
+            last_query: None,
+        }
+    }
+
+    fn is_stale(&self) -> bool {
+        self.walked
+            .is_none_or(|walked| walked.elapsed() >= REFRESH_INTERVAL)
+    }
+
+    // Most walks find the files of the previous one, their candidates are kept as they
+    // are and so is the last query when nothing changed
+    fn install(&mut self, files: Vec<PathBuf>) {
+        self.indexed = true;
+        let unchanged = self.candidates.len() == files.len()
+            && self
+                .candidates
+                .iter()
+                .zip(&files)
+                .all(|(candidate, path)| candidate.path == *path);
+        if unchanged {
+            return;
+        }
+
+        let mut previous: HashMap<PathBuf, Candidate> = std::mem::take(&mut self.candidates)
+            .into_iter()
+            .map(|candidate| (candidate.path.clone(), candidate))
+            .collect();
+        self.candidates = files
+            .into_iter()
+            .map(|path| {
+                previous
+                    .remove(&path)
+                    .unwrap_or_else(|| Candidate::new(&self.root, path))
+            })
+            .collect();
+        self.last_query = None;
+    }
+
+    fn search(&mut self, query: &FuzzyQuery) -> Vec<(i32, u32)> {
+        let scope: Vec<u32> = match &self.last_query {
+            Some((last_query, matched)) if query.extends(last_query) => matched.clone(),
+            _ => (0..self.candidates.len() as u32).collect(),
+        };
+
+        let mut scored = vec![];
+        for index in scope {
+            let candidate = &self.candidates[index as usize];
+            if let Some(score) = fuzzy_score(
+                query,
+                &candidate.chars,
+                candidate.mask,
+                candidate.basename_start,
+            ) {
+                scored.push((score, index));
+            }
+        }
+
+        self.last_query = Some((
+            query.clone(),
+            scored.iter().map(|(_, index)| *index).collect(),
+        ));
+        scored
+    }
+
+    fn emit(&self, index: u32, query: &FuzzyQuery) -> Option<QuickOpenItem> {
+        let candidate = self.candidates.get(index as usize)?;
+        let matched = fuzzy_match(
+            query,
+            &candidate.chars,
+            candidate.mask,
+            candidate.basename_start,
+        )?;
+        Some(QuickOpenItem {
+            path: candidate.path.clone(),
+            relative_path: candidate.relative_path.clone(),
+            score: matched.score,
+            positions: matched.positions,
+        })
+    }
+}
+
+/// A walk of `root` that skips what project-wide search skips: hidden files, the
+/// entries of .gitignore and similar files, and `IGNORED_DIRECTORIES`.
+pub fn workspace_walker(root: &Path) -> WalkBuilder {
+    let mut walker = WalkBuilder::new(root);
+    walker
+        .standard_filters(true)
+        // Also honour .gitignore files outside of a git repository
+        .require_git(false)
+        .filter_entry(|entry| {
+            let is_dir = entry
+                .file_type()
+                .is_some_and(|file_type| file_type.is_dir());
+            !(is_dir
+                && entry
+                    .file_name()
+                    .to_str()
+                    .is_some_and(|name| IGNORED_DIRECTORIES.contains(&name)))
+        });
+    walker
+}
+
+/// Every file below `root`, sorted. Unreadable entries are left out rather than
+/// failing the whole walk.
+pub fn walk_files(root: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = workspace_walker(root)
+        .build()
+        .flatten()
+        .filter(|entry| {
+            entry
+                .file_type()
+                .is_some_and(|file_type| file_type.is_file())
+        })
+        .map(|entry| entry.into_path())
+        .collect();
+    files.sort();
+    files
+}
+
+/// Roots waiting for the file walker thread.
+#[derive(Debug, Default)]
+pub struct WalkQueue {
+    roots: Mutex<HashSet<PathBuf>>,
+    ready: Condvar,
+}
+
+impl WalkQueue {
+    pub fn push(&self, roots: Vec<PathBuf>) -> Result<(), MutexLockError> {
+        if roots.is_empty() {
+            return Ok(());
+        }
+        self.roots
+            .lock()
+            .map_err(|err| MutexLockError(err.to_string()))?
+            .extend(roots);
+        self.ready.notify_one();
+        Ok(())
+    }
+
+    /// Waits for roots to walk and takes all of them, none once the queue is poisoned.
+    pub fn next(&self) -> Option<Vec<PathBuf>> {
+        let mut roots = self.roots.lock().ok()?;
+        while roots.is_empty() {
+            roots = self.ready.wait(roots).ok()?;
+        }
+        Some(roots.drain().collect())
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct FileIndex {
+    roots: HashMap<PathBuf, RootIndex>,
+}
+
+impl FileIndex {
+    pub fn new() -> Self {
+        Self {
+            roots: HashMap::default(),
+        }
+    }
+
+    pub fn roots(&self) -> Vec<PathBuf> {
+        self.roots.keys().cloned().collect()
+    }
+
+    /// Replaces the indexed roots, keeping the already built index of roots that stay.
+    pub fn set_roots(&mut self, roots: Vec<PathBuf>) {
+        self.roots.retain(|root, _| roots.contains(root));
+        for root in roots {
+            self.add_root(root);
+        }
+    }
+
+    pub fn add_root(&mut self, root: PathBuf) {
+        self.roots
+            .entry(root.clone())
+            .or_insert_with(|| RootIndex::new(root));
+    }
+
+    pub fn remove_root(&mut self, root: &Path) {
+        self.roots.remove(root);
+    }
+
+    /// Roots due for another walk, counted as walked from now on so that every
+    /// keystroke does not queue them again.
+    pub fn stale_roots(&mut self) -> Vec<PathBuf> {
+        self.roots
+            .values_mut()
+            .filter(|root_index| root_index.is_stale())
+            .map(|root_index| {
+                root_index.walked = Some(Instant::now());
+                root_index.root.clone()
+            })
+            .collect()
+    }
+
+    /// Replaces the files of `root` with the result of a walk, unless it was removed
+    /// in the meantime.
+    pub fn install(&mut self, root: &Path, files: Vec<PathBuf>) {
+        if let Some(root_index) = self.roots.get_mut(root) {
+            root_index.install(files);
+        }
+    }
+
+    pub fn quick_open(&mut self, query: &str, limit: usize) -> QuickOpenResults {
+        let query = FuzzyQuery::new(query);
+        let roots: Vec<PathBuf> = self.roots();
+
+        // (score, path length, root position, candidate index)
+        let mut scored: Vec<(i32, usize, usize, u32)> = vec![];
+        for (position, root) in roots.iter().enumerate() {
+            let Some(root_index) = self.roots.get_mut(root) else {
+                continue;
+            };
+            for (score, index) in root_index.search(&query) {
+                let length = root_index.candidates[index as usize].chars.len();
+                scored.push((score, length, position, index));
+            }
+        }
+
+        let root_indexes: Vec<&RootIndex> = roots.iter().map(|root| &self.roots[root]).collect();
+        let relative_path =
+            |root: usize, index: u32| &root_indexes[root].candidates[index as usize].relative_path;
+
+        // Best score first, then shorter paths, then alphabetical for a stable order
+        scored.sort_by(|a, b| {
+            b.0.cmp(&a.0)
+                .then(a.1.cmp(&b.1))
+                .then_with(|| relative_path(a.2, a.3).cmp(relative_path(b.2, b.3)))
+        });
+
+        QuickOpenResults {
+            items: scored
+                .into_iter()
+                .take(limit)
+                .filter_map(|(_, _, root, index)| root_indexes[root].emit(index, &query))
+                .collect(),
+            indexing: root_indexes.iter().any(|root_index| !root_index.indexed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::{Path, PathBuf};
+
+    use super::{Candidate, FileIndex};
+
+    fn index(files: &[&str]) -> FileIndex {
+        let mut file_index = FileIndex::new();
+        file_index.add_root(PathBuf::from("/root"));
+        file_index.install(
+            Path::new("/root"),
+            files
+                .iter()
+                .map(|file| Path::new("/root").join(file))
+                .collect(),
+        );
+        file_index
+    }
+
+    fn relative_paths(file_index: &mut FileIndex, query: &str) -> Vec<String> {
+        let results = file_index.quick_open(query, 10);
+        assert!(!results.indexing);
+        results
+            .items
+            .into_iter()
+            .map(|item| item.relative_path)
+            .collect()
+    }
+
+    #[test]
+    fn ranks_basenames_then_shorter_paths() {
+        let mut file_index = index(&[
+            "src/my_animation.rs",
+            "src/backend/main.rs",
+            "src/main.rs",
+            "docs/readme.md",
+        ]);
+
+        assert_eq!(
+            relative_paths(&mut file_index, "main"),
+            ["src/main.rs", "src/backend/main.rs", "src/my_animation.rs"]
+        );
+    }
+
+    #[test]
+    fn reports_roots_until_their_first_walk() {
+        let mut file_index = FileIndex::new();
+        file_index.set_roots(vec![PathBuf::from("/a"), PathBuf::from("/b")]);
+
+        let mut stale = file_index.stale_roots();
+        stale.sort();
+        assert_eq!(stale, [PathBuf::from("/a"), PathBuf::from("/b")]);
+        // Queued walks are not queued again on the next keystroke
+        assert!(file_index.stale_roots().is_empty());
+        assert!(file_index.quick_open("a", 10).indexing);
+
+        file_index.install(Path::new("/a"), vec![PathBuf::from("/a/main.rs")]);
+        assert!(file_index.quick_open("a", 10).indexing);
+        file_index.install(Path::new("/b"), vec![]);
+        assert!(!file_index.quick_open("a", 10).indexing);
+    }
+
+    #[test]
+    fn narrows_down_the_matches_of_the_last_query() {
+        let mut file_index = index(&["src/main.rs", "src/lib.rs"]);
+        assert_eq!(relative_paths(&mut file_index, "ma"), ["src/main.rs"]);
+
+        // Only the candidates matched by "ma" are searched for "mai"
+        let root_index = file_index.roots.get_mut(Path::new("/root")).unwrap();
+        root_index
+            .candidates
+            .push(Candidate::new(Path::new("/root"), "/root/maid.rs".into()));
+        assert_eq!(relative_paths(&mut file_index, "mai"), ["src/main.rs"]);
+        assert_eq!(
+            relative_paths(&mut file_index, "i"),
+            ["maid.rs", "src/lib.rs", "src/main.rs"]
+        );
+
+        // A walk that finds the same files keeps the last query
+        file_index.install(
+            Path::new("/root"),
+            ["/root/src/main.rs", "/root/src/lib.rs", "/root/maid.rs"]
+                .map(PathBuf::from)
+                .to_vec(),
+        );
+        let root_index = &file_index.roots[Path::new("/root")];
+        assert!(root_index.last_query.is_some());
+    }
+}
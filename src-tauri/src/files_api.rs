@@ -241,29 +241,45 @@ impl DirectoryItem {
     }
 }
 
-pub fn get_directory_items(dir: &Path, recursion: u32) -> Result<Vec<DirectoryItem>, Error> {
-    let mut directory_item: Vec<DirectoryItem> = vec![];
+#[derive(Debug)]
+pub struct ListedEntry {
+    pub is_file: bool,
+    pub name: String,
+    pub path: PathBuf,
+}
+
+pub fn list_directory(dir: &Path) -> Result<Vec<ListedEntry>, Error> {
+    let mut listed_entries: Vec<ListedEntry> = vec![];
     let entries = dir.read_dir()?;
 
     for entry in entries.flatten() {
-        if entry.file_type()?.is_file() {
-            directory_item.push(DirectoryItem::create_file(
-                entry
-                    .file_name()
-                    .into_string()
-                    .map_err(|_err| PathError::ToStringError)?,
-                entry.path().absolutize()?.to_path_buf(),
-            ))
-        } else if entry.file_type().unwrap().is_dir() && recursion > 0 {
+        let file_type = entry.file_type()?;
+        if !file_type.is_file() && !file_type.is_dir() {
+            continue;
+        }
+        listed_entries.push(ListedEntry {
+            is_file: file_type.is_file(),
+            name: entry
+                .file_name()
+                .into_string()
+                .map_err(|_err| PathError::ToStringError)?,
+            path: entry.path().absolutize()?.to_path_buf(),
+        });
+    }
+    Ok(listed_entries)
+}
+
+pub fn get_directory_items(dir: &Path, recursion: u32) -> Result<Vec<DirectoryItem>, Error> {
+    let mut directory_item: Vec<DirectoryItem> = vec![];
+
+    for entry in list_directory(dir)? {
+        if entry.is_file {
+            directory_item.push(DirectoryItem::create_file(entry.name, entry.path))
+        } else if recursion > 0 {
             let recursion = recursion - 1;
-            let item = get_directory_items(&entry.path(), recursion)?;
+            let item = get_directory_items(&entry.path, recursion)?;
             directory_item.push(DirectoryItem::create_directory(
-                entry
-                    .file_name()
-                    .into_string()
-                    .map_err(|_err| PathError::ToStringError)?,
-                entry.path().absolutize()?.to_path_buf(),
-                item,
+                entry.name, entry.path, item,
             ));
         }
     }
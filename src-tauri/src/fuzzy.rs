@@ -0,0 +1,285 @@
+// Subsequence based fuzzy matching shared by quick open and symbol lookups.
+//
+// A query matches a candidate when every query character appears in the candidate
+// in order (case insensitive). Among all possible alignments the one with the best
+// score is picked, rewarding matches at the start of path segments, after word
+// separators, on camelCase humps and runs of consecutive characters.
+
+const SCORE_MATCH: i32 = 16;
+const BONUS_SEGMENT_START: i32 = 72;
+const BONUS_WORD_START: i32 = 48;
+const BONUS_CAMEL_CASE: i32 = 40;
+const BONUS_CONSECUTIVE: i32 = 32;
+const BONUS_BASENAME: i32 = 8;
+const PENALTY_GAP: i32 = 3;
+const PENALTY_LEADING: i32 = 1;
+const MAX_LEADING_PENALTY: i32 = 12;
+const NO_MATCH: i32 = i32::MIN / 2;
+
+#[derive(Debug, Clone, Default)]
+pub struct FuzzyQuery {
+    chars: Vec<char>,
+    mask: u64,
+}
+
+impl FuzzyQuery {
+    pub fn new(query: &str) -> Self {
+        let chars: Vec<char> = query
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .map(fold_case)
+            .collect();
+        let mask = char_mask(chars.iter().copied());
+        Self { chars, mask }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.chars.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.chars.len()
+    }
+
+    /// Whether every candidate matching `self` also has to match `other`'s prefix,
+    /// which allows narrowing down the candidates of a previous keystroke.
+    pub fn extends(&self, other: &FuzzyQuery) -> bool {
+        self.chars.starts_with(&other.chars)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FuzzyMatch {
+    pub score: i32,
+    /// Char indices of the candidate that were matched by the query.
+    pub positions: Vec<u32>,
+}
+
+/// Bit set of the (case folded) characters of a string. A candidate can only match
+/// if its mask contains every bit of the query mask.
+pub fn char_mask(chars: impl Iterator<Item = char>) -> u64 {
+    chars.fold(0, |mask, c| {
+        let c = fold_case(c);
+        let bit = match c {
+            'a'..='z' => c as u32 - 'a' as u32,
+            '0'..='9' => 26 + c as u32 - '0' as u32,
+            _ => 63,
+        };
+        mask | (1 << bit)
+    })
+}
+
+pub fn fold_case(c: char) -> char {
+    if c.is_ascii() {
+        c.to_ascii_lowercase()
+    } else {
+        c.to_lowercase().next().unwrap_or(c)
+    }
+}
+
+/// Scores `candidate` against `query` without computing the match positions.
+pub fn fuzzy_score(
+    query: &FuzzyQuery,
+    candidate: &[char],
+    candidate_mask: u64,
+    basename_start: usize,
+) -> Option<i32> {
+    align(query, candidate, candidate_mask, basename_start, false).map(|matched| matched.score)
+}
+
+/// Scores `candidate` against `query` and returns the positions of the best alignment.
+pub fn fuzzy_match(
+    query: &FuzzyQuery,
+    candidate: &[char],
+    candidate_mask: u64,
+    basename_start: usize,
+) -> Option<FuzzyMatch> {
+    align(query, candidate, candidate_mask, basename_start, true)
+}
+
+fn align(
+    query: &FuzzyQuery,
+    candidate: &[char],
+    candidate_mask: u64,
+    basename_start: usize,
+    with_positions: bool,
+) -> Option<FuzzyMatch> {
+    let query_chars = &query.chars;
+    let n = query_chars.len();
+    let m = candidate.len();
+
+    if n == 0 {
+        return Some(FuzzyMatch {
+            score: 0,
+            positions: vec![],
+        });
+    }
+    if query.mask & !candidate_mask != 0 || n > m || !is_subsequence(query_chars, candidate) {
+        return None;
+    }
+
+    let bonuses: Vec<i32> = (0..m)
+        .map(|index| position_bonus(candidate, index, basename_start))
+        .collect();
+
+    // prev[j] / current[j] hold the best score of an alignment whose last matched
+    // query character sits on candidate[j]
+    let mut prev = vec![NO_MATCH; m];
+    let mut current = vec![NO_MATCH; m];
+    let mut back = if with_positions {
+        vec![0u32; n * m]
+    } else {
+        vec![]
+    };
+
+    for (i, query_char) in query_chars.iter().enumerate() {
+        // Best score of prev[k] for k <= j - 2 including the gap penalty up to j
+        let mut gap_best = NO_MATCH;
+        let mut gap_from = 0;
+
+        for j in 0..m {
+            if j >= 2 && prev[j - 2] > NO_MATCH {
+                let from_gap = prev[j - 2] - PENALTY_GAP;
+                if from_gap > gap_best - PENALTY_GAP {
+                    gap_best = from_gap;
+                    gap_from = j - 2;
+                } else {
+                    gap_best -= PENALTY_GAP;
+                }
+            } else if gap_best > NO_MATCH {
+                gap_best -= PENALTY_GAP;
+            }
+
+            current[j] = NO_MATCH;
+            if fold_case(candidate[j]) != *query_char {
+                continue;
+            }
+
+            let base = SCORE_MATCH + bonuses[j];
+            if i == 0 {
+                let leading = (j as i32 * PENALTY_LEADING).min(MAX_LEADING_PENALTY);
+                current[j] = base - leading;
+                continue;
+            }
+
+            let consecutive = if j >= 1 && prev[j - 1] > NO_MATCH {
+                prev[j - 1] + BONUS_CONSECUTIVE
+            } else {
+                NO_MATCH
+            };
+
+            let (best, from) = if consecutive >= gap_best {
+                (consecutive, j.saturating_sub(1))
+            } else {
+                (gap_best, gap_from)
+            };
+            if best <= NO_MATCH {
+                continue;
+            }
+
+            current[j] = best + base;
+            if with_positions {
+                back[i * m + j] = from as u32;
+            }
+        }
+        std::mem::swap(&mut prev, &mut current);
+    }
+
+    let (end, score) = prev
+        .iter()
+        .enumerate()
+        .filter(|(_, score)| **score > NO_MATCH)
+        .max_by(|a, b| a.1.cmp(b.1).then(b.0.cmp(&a.0)))?;
+
+    let mut positions = vec![];
+    if with_positions {
+        positions = vec![0u32; n];
+        let mut position = end as u32;
+        for i in (0..n).rev() {
+            positions[i] = position;
+            if i > 0 {
+                position = back[i * m + position as usize];
+            }
+        }
+    }
+
+    Some(FuzzyMatch {
+        score: *score,
+        positions,
+    })
+}
+
+fn is_subsequence(query: &[char], candidate: &[char]) -> bool {
+    let mut query = query.iter().peekable();
+    for c in candidate {
+        if query.peek().is_some_and(|q| **q == fold_case(*c)) {
+            query.next();
+        }
+    }
+    query.peek().is_none()
+}
+
+fn position_bonus(candidate: &[char], index: usize, basename_start: usize) -> i32 {
+    let current = candidate[index];
+    let basename = if index >= basename_start {
+        BONUS_BASENAME
+    } else {
+        0
+    };
+    if index == 0 {
+        return BONUS_SEGMENT_START + basename;
+    }
+
+    let previous = candidate[index - 1];
+    let bonus = match previous {
+        '/' | '\\' => BONUS_SEGMENT_START,
+        '_' | '-' | '.' | ' ' => BONUS_WORD_START,
+        _ if previous.is_lowercase() && current.is_uppercase() => BONUS_CAMEL_CASE,
+        _ if !previous.is_ascii_digit() && current.is_ascii_digit() => BONUS_WORD_START / 2,
+        _ => 0,
+    };
+    bonus + basename
+}
+
+#[cfg(test)]
+mod test {
+    use super::{FuzzyQuery, char_mask, fuzzy_match};
+
+    fn run(query: &str, candidate: &str) -> Option<(i32, Vec<u32>)> {
+        let chars: Vec<char> = candidate.chars().collect();
+        let basename_start = candidate.rfind('/').map(|index| index + 1).unwrap_or(0);
+        fuzzy_match(
+            &FuzzyQuery::new(query),
+            &chars,
+            char_mask(chars.iter().copied()),
+            basename_start,
+        )
+        .map(|matched| (matched.score, matched.positions))
+    }
+
+    #[test]
+    fn rejects_non_subsequence() {
+        assert!(run("xyz", "src/main.rs").is_none());
+        assert!(run("mian", "src/main.rs").is_none());
+    }
+
+    #[test]
+    fn prefers_segment_and_camel_case_starts() {
+        let (_, positions) = run("fe", "src/component/fileExplorer.tsx").unwrap();
+        assert_eq!(positions, vec![14, 18]);
+
+        let (_, positions) = run("tb", "src/treesitter_backend/mod.rs").unwrap();
+        assert_eq!(positions, vec![4, 15]);
+    }
+
+    #[test]
+    fn ranks_basename_and_consecutive_matches_higher() {
+        let (exact, _) = run("main", "src/main.rs").unwrap();
+        let (scattered, _) = run("main", "src/my_animation.rs").unwrap();
+        assert!(exact > scattered);
+
+        let (basename, _) = run("mod", "src/treesitter_backend/mod.rs").unwrap();
+        let (directory, _) = run("mod", "src/modules/lib.rs").unwrap();
+        assert!(basename > directory);
+    }
+}
@@ -3,12 +3,15 @@ use std::{path::PathBuf, sync::Mutex};
 use crate::files_api::FileManager;
 use app_config::EditorConfig;
 use error::Error;
+use file_index::{FileIndex, WalkQueue};
 use treesitter_backend::{parser::ParserHelper, query::QueryManager};
 
 pub mod app_config;
 pub mod backend_api;
 pub mod error;
+pub mod file_index;
 pub mod files_api;
+pub mod fuzzy;
 pub mod language;
 pub mod treesitter_backend;
 
@@ -16,7 +19,9 @@ pub mod treesitter_backend;
 pub struct StateManager {
     pub editor_config: Mutex<EditorConfig>,
     pub file_manager: Mutex<FileManager>,
+    pub file_index: Mutex<FileIndex>,
     parser_helper: Mutex<ParserHelper>,
+    walk_queue: WalkQueue,
     query_iter: QueryManager,
 }
 
@@ -24,8 +29,10 @@ impl StateManager {
     pub fn new(path: Option<PathBuf>) -> Result<Self, Error> {
         Ok(Self {
             file_manager: Mutex::new(FileManager::new()),
+            file_index: Mutex::new(FileIndex::new()),
             editor_config: Mutex::new(EditorConfig::load(path)?),
             parser_helper: Mutex::new(ParserHelper::default()),
+            walk_queue: WalkQueue::default(),
             query_iter: QueryManager::default(),
         })
     }
@@ -3,7 +3,10 @@
 
 use branchy_text_editor::{
     StateManager,
-    backend_api::middleware::{editor::*, file_data::*, file_system::*},
+    backend_api::{
+        middleware::{editor::*, file_data::*, file_system::*},
+        walking::spawn_file_walker,
+    },
 };
 use specta_typescript::Typescript;
 use tauri::Manager;
@@ -24,7 +27,8 @@ fn main() {
             get_editor_config,
             get_tokens_legend,
             set_highlights,
-            get_currently_supported_language
+            get_currently_supported_language,
+            quick_open
         ]);
 
     #[cfg(debug_assertions)] // <- Only export on non-release builds
@@ -43,6 +47,7 @@ fn main() {
             let config_dir = app_handle.path().app_local_data_dir().ok();
             let state_manager = StateManager::new(config_dir)?;
             app_handle.manage(state_manager);
+            spawn_file_walker(app_handle.clone());
             Ok(())
         })
         .run(tauri::generate_context!())
@@ -40,6 +40,9 @@ async setHighlights(id: number, rangedSourceCode: string, range: RangePoint) : P
 },
 async getCurrentlySupportedLanguage() : Promise<Lang[]> {
     return await TAURI_INVOKE("get_currently_supported_language");
+},
+async quickOpen(query: string) : Promise<Response<QuickOpenResults>> {
+    return await TAURI_INVOKE("quick_open", { query });
 }
 }
 
@@ -67,6 +70,8 @@ export type Lang = "Javascript" | "Typescript" | "Rust" | "Python" | "Java" | "R
 export type LanguageTheme = { default: Theme<Basic>; javascript: Theme<Javascript> | null; rust: Theme<Rust> | null; java: Theme<Java> | null; html: Theme<Html> | null; css: Theme<Css> | null; python: Theme<Python> | null; ruby: Theme<Ruby> | null }
 export type OpenFile = { id: number; name: string; language: Lang | null; same_name_exist: boolean; path: string }
 export type Python = null
+export type QuickOpenItem = { path: string; relative_path: string; score: number; positions: number[] }
+export type QuickOpenResults = { items: QuickOpenItem[]; indexing: boolean }
 export type RangePoint = [number, number, number, number]
 export type Response<T> = { Success: T } | { Error: string }
 export type Ruby = null